@@ -1,8 +1,11 @@
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::{client::BaseClient, Response, StripeError};
 
 use http_types::{Request, StatusCode};
+use rand::Rng;
 use serde::{de::DeserializeOwned, Serialize};
 
 #[derive(Clone, Debug)]
@@ -17,7 +20,202 @@ pub enum RequestStrategy {
     /// This strategy will retry the request up to the
     /// specified number of times using the same, random,
     /// idempotency key with exponential backoff, up to n times.
-    ExponentialBackoff(u64),
+    ///
+    /// The delay before the `x`-th retry is `min(max_delay, base * 2^x)`.
+    /// When `jitter` is set the delay is instead sampled uniformly from
+    /// `[0, min(max_delay, base * 2^x)]` ("full jitter"), which spreads
+    /// retries from many clients out over time instead of firing them in
+    /// lockstep. Disable it when you need the schedule to be deterministic.
+    ///
+    /// `total_budget`, when set, is a wall-clock ceiling on the time spent
+    /// waiting across all attempts: once the elapsed time reaches it the
+    /// strategy stops, and a delay that would run past it is clamped to the
+    /// time that remains so the total never overshoots. This gives request-path code a hard latency bound regardless
+    /// of how the exponential schedule grows.
+    ExponentialBackoff {
+        max_retries: u64,
+        base: Duration,
+        max_delay: Duration,
+        jitter: bool,
+        total_budget: Option<Duration>,
+    },
+    /// This strategy retries with *decorrelated* jitter: each delay is derived
+    /// from the previous one as `min(cap, random_between(base, prev * 3))`,
+    /// seeded with `prev = base`. Compared to the `2^x` schedule this spreads
+    /// retry traffic more smoothly, which helps high-concurrency workloads that
+    /// would otherwise synchronize against Stripe's rate limiter.
+    DecorrelatedJitter {
+        max_retries: u64,
+        base: Duration,
+        cap: Duration,
+        total_budget: Option<Duration>,
+    },
+    /// Wrap another strategy with a caller-supplied predicate that decides
+    /// whether a given failure is worth retrying. The predicate composes with
+    /// the `stripe_should_retry` short-circuit and `inner`'s max-attempt limit:
+    /// it only gets a say once Stripe hasn't vetoed the retry, and a `true`
+    /// result still defers the schedule (delay, attempt count) to `inner`.
+    ///
+    /// This mirrors the `retry_if` helper from the `again`/`backon` crates, so
+    /// users can, for example, retry a `lock_timeout` error or a specific `5xx`
+    /// while still giving up on genuine validation failures.
+    RetryIf {
+        inner: Box<RequestStrategy>,
+        predicate: RetryPredicate,
+    },
+    /// Retry according to a fully configurable [`RetryConfig`], modelled after
+    /// the taskcluster client's `Retry` struct. Unlike
+    /// [`ExponentialBackoff`](RequestStrategy::ExponentialBackoff)'s on/off
+    /// full jitter, the delay is spread by a tunable `randomization_factor`
+    /// around `delay_factor * 2^x`, and a single config can be shared across
+    /// many `Client` calls.
+    Configured(RetryConfig),
+}
+
+/// Tunable retry behavior shared across `Client` calls.
+///
+/// The delay before the `attempt`-th retry is
+/// `min(max_delay, delay_factor * 2^attempt)`, then multiplied by a random
+/// value in `[1 - randomization_factor, 1 + randomization_factor]` and clamped
+/// back to `max_delay`. A `randomization_factor` of `0.0` disables jitter,
+/// which keeps the schedule deterministic for tests.
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    pub retries: u64,
+    pub max_delay: Duration,
+    pub delay_factor: Duration,
+    pub randomization_factor: f64,
+    pub total_budget: Option<Duration>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            retries: 3,
+            max_delay: Duration::from_secs(30),
+            delay_factor: Duration::from_secs(1),
+            randomization_factor: 0.5,
+            total_budget: None,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// A config that retries up to `retries` times with the default schedule.
+    pub fn new(retries: u64) -> Self {
+        RetryConfig { retries, ..Default::default() }
+    }
+
+    /// Set the ceiling on any single delay.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set the base delay multiplied by `2^attempt`.
+    pub fn delay_factor(mut self, delay_factor: Duration) -> Self {
+        self.delay_factor = delay_factor;
+        self
+    }
+
+    /// Set the jitter spread; `0.0` disables randomization.
+    pub fn randomization_factor(mut self, randomization_factor: f64) -> Self {
+        self.randomization_factor = randomization_factor;
+        self
+    }
+
+    /// Set a wall-clock ceiling on the total time spent retrying.
+    pub fn total_budget(mut self, total_budget: Duration) -> Self {
+        self.total_budget = Some(total_budget);
+        self
+    }
+
+    /// Turn this config into a [`RequestStrategy::Configured`].
+    pub fn strategy(self) -> RequestStrategy {
+        RequestStrategy::Configured(self)
+    }
+
+    /// The delay before the `attempt`-th retry under this config.
+    fn backoff(&self, attempt: u64) -> Duration {
+        let capped = match u32::try_from(attempt)
+            .ok()
+            .and_then(|n| 1u32.checked_shl(n))
+            .and_then(|factor| self.delay_factor.checked_mul(factor))
+        {
+            Some(d) if d <= self.max_delay => d,
+            _ => self.max_delay,
+        };
+
+        if self.randomization_factor <= 0.0 {
+            return capped;
+        }
+
+        // multiply by a random factor in [1 - f, 1 + f], then re-clamp.
+        let f = self.randomization_factor;
+        let multiplier = rand::thread_rng().gen_range((1.0 - f)..=(1.0 + f)).max(0.0);
+        let scaled = capped.mul_f64(multiplier);
+        scaled.min(self.max_delay)
+    }
+}
+
+/// A caller-supplied classifier for [`RequestStrategy::RetryIf`]: given the
+/// response status and the decoded error (when available), return `true` to
+/// retry the request or `false` to stop.
+#[derive(Clone)]
+pub struct RetryPredicate(Arc<dyn Fn(StatusCode, Option<&StripeError>) -> bool + Send + Sync>);
+
+impl RetryPredicate {
+    pub fn new(
+        predicate: impl Fn(StatusCode, Option<&StripeError>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        RetryPredicate(Arc::new(predicate))
+    }
+
+    fn should_retry(&self, status: StatusCode, error: Option<&StripeError>) -> bool {
+        (self.0)(status, error)
+    }
+}
+
+impl fmt::Debug for RetryPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RetryPredicate(..)")
+    }
+}
+
+impl RequestStrategy {
+    /// An [`ExponentialBackoff`](RequestStrategy::ExponentialBackoff) strategy
+    /// with the default `1s` base, `30s` cap and full jitter enabled.
+    pub fn exponential_backoff(max_retries: u64) -> Self {
+        RequestStrategy::ExponentialBackoff {
+            max_retries,
+            base: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            total_budget: None,
+        }
+    }
+
+    /// The total wall-clock retry budget this strategy carries, if any.
+    fn total_budget(&self) -> Option<Duration> {
+        match self {
+            RequestStrategy::ExponentialBackoff { total_budget, .. }
+            | RequestStrategy::DecorrelatedJitter { total_budget, .. } => *total_budget,
+            RequestStrategy::Configured(config) => config.total_budget,
+            RequestStrategy::RetryIf { inner, .. } => inner.total_budget(),
+            _ => None,
+        }
+    }
+
+    /// Wrap `self` with a predicate that decides whether a failure is retryable.
+    pub fn retry_if(
+        self,
+        predicate: impl Fn(StatusCode, Option<&StripeError>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        RequestStrategy::RetryIf {
+            inner: Box::new(self),
+            predicate: RetryPredicate::new(predicate),
+        }
+    }
 }
 
 impl RequestStrategy {
@@ -26,13 +224,52 @@ impl RequestStrategy {
         status: Option<StatusCode>,
         stripe_should_retry: Option<bool>,
         retry_count: u64,
+        prev_delay: Option<Duration>,
+        error: Option<&StripeError>,
+        retry_after: Option<Duration>,
+        elapsed: Duration,
     ) -> Outcome {
         // if stripe explicitly says not to retry then don't
         if !stripe_should_retry.unwrap_or(true) {
             return Outcome::Stop;
         }
 
-        match (self, status, retry_count) {
+        // a wrapping predicate gets to reclassify the failure before the inner
+        // strategy's schedule kicks in.
+        if let RequestStrategy::RetryIf { inner, predicate } = self {
+            return match status {
+                // only failures are classified; successes defer to the inner
+                // strategy as usual (which handles the initial run etc.)
+                Some(s) if !s.is_success() => {
+                    if predicate.should_retry(s, error) {
+                        // clear the status so the inner strategy doesn't apply
+                        // its own client-error stop, and just yields a schedule.
+                        inner.test(
+                            None,
+                            stripe_should_retry,
+                            retry_count,
+                            prev_delay,
+                            error,
+                            retry_after,
+                            elapsed,
+                        )
+                    } else {
+                        Outcome::Stop
+                    }
+                }
+                _ => inner.test(
+                    status,
+                    stripe_should_retry,
+                    retry_count,
+                    prev_delay,
+                    error,
+                    retry_after,
+                    elapsed,
+                ),
+            };
+        }
+
+        let outcome = match (self, status, retry_count) {
             // a strategy of once or idempotent should run once
             (RequestStrategy::Once | RequestStrategy::Idempotent(_), _, 0) => {
                 Outcome::Continue(None)
@@ -41,23 +278,66 @@ impl RequestStrategy {
             // requests with idempotency keys that hit client
             // errors usually cannot be solved with retries
             // see: https://stripe.com/docs/error-handling#content-errors
+            //
+            // `429 Too Many Requests` is the exception: it's explicitly
+            // retryable, so let it fall through to the schedule below.
             (
                 RequestStrategy::Retry(_)
                 | RequestStrategy::Idempotent(_)
-                | RequestStrategy::ExponentialBackoff(_),
+                | RequestStrategy::ExponentialBackoff { .. }
+                | RequestStrategy::DecorrelatedJitter { .. }
+                | RequestStrategy::Configured(_),
                 Some(c),
                 _,
-            ) if c.is_client_error() => Outcome::Stop,
+            ) if c.is_client_error() && c != StatusCode::TooManyRequests => Outcome::Stop,
 
             // a strategy of retry or exponential backoff should retry with
             // the appropriate delay if the number of retries is less than the max
             (RequestStrategy::Retry(n), _, x) if x < *n => Outcome::Continue(None),
-            (RequestStrategy::ExponentialBackoff(n), _, x) if x < *n => {
-                Outcome::Continue(Some(calculate_backoff(x)))
+            (
+                RequestStrategy::ExponentialBackoff {
+                    max_retries, base, max_delay, jitter, total_budget: _
+                },
+                _,
+                x,
+            ) if x < *max_retries => {
+                Outcome::Continue(Some(calculate_backoff(x, *base, *max_delay, *jitter)))
+            }
+            (RequestStrategy::DecorrelatedJitter { max_retries, base, cap, total_budget: _ }, _, x)
+                if x < *max_retries =>
+            {
+                // seed from `base` on the first attempt, then feed each delay
+                // back into the next one.
+                let prev = prev_delay.unwrap_or(*base);
+                Outcome::Continue(Some(decorrelated_backoff(*base, *cap, prev)))
+            }
+            (RequestStrategy::Configured(config), _, x) if x < config.retries => {
+                Outcome::Continue(Some(config.backoff(x)))
             }
 
             // unknown cases should be stopped to prevent infinite loops
             _ => Outcome::Stop,
+        };
+
+        // when the server told us how long to wait (e.g. the `Retry-After`
+        // header on a 429), honor it in place of the computed backoff.
+        let outcome = match (outcome, retry_after) {
+            (Outcome::Continue(_), Some(delay)) => Outcome::Continue(Some(delay)),
+            (outcome, _) => outcome,
+        };
+
+        // enforce the total retry time-budget: stop once it's spent, otherwise
+        // clamp the next delay so the accumulated wait never overshoots it.
+        match (self.total_budget(), outcome) {
+            (Some(budget), Outcome::Continue(Some(delay))) => {
+                let remaining = budget.saturating_sub(elapsed);
+                if remaining.is_zero() {
+                    Outcome::Stop
+                } else {
+                    Outcome::Continue(Some(delay.min(remaining)))
+                }
+            }
+            (_, outcome) => outcome,
         }
     }
 
@@ -65,22 +345,65 @@ impl RequestStrategy {
         match self {
             RequestStrategy::Once => None,
             RequestStrategy::Idempotent(key) => Some(key.clone()),
+            RequestStrategy::RetryIf { inner, .. } => inner.get_key(),
             #[cfg(feature = "uuid")]
-            RequestStrategy::Retry(_) | RequestStrategy::ExponentialBackoff(_) => {
-                Some(uuid::Uuid::new_v4().to_string())
-            }
+            RequestStrategy::Retry(_)
+            | RequestStrategy::ExponentialBackoff { .. }
+            | RequestStrategy::DecorrelatedJitter { .. }
+            | RequestStrategy::Configured(_) => Some(uuid::Uuid::new_v4().to_string()),
             #[cfg(not(feature = "uuid"))]
-            RequestStrategy::Retry(_) | RequestStrategy::ExponentialBackoff(_) => None,
+            RequestStrategy::Retry(_)
+            | RequestStrategy::ExponentialBackoff { .. }
+            | RequestStrategy::DecorrelatedJitter { .. }
+            | RequestStrategy::Configured(_) => None,
         }
     }
 }
 
-fn calculate_backoff(retry_count: u64) -> Duration {
-    let mut duration = Duration::from_secs(1);
-    for _ in 0..retry_count {
-        duration = duration * 2;
+fn calculate_backoff(retry_count: u64, base: Duration, max_delay: Duration, jitter: bool) -> Duration {
+    // capped = min(max_delay, base * 2^retry_count), guarding against overflow
+    // of the exponential term on large retry counts. The shift itself can
+    // overflow for `retry_count >= 32`, so guard it before `checked_mul`.
+    let capped = match u32::try_from(retry_count)
+        .ok()
+        .and_then(|n| 1u32.checked_shl(n))
+        .and_then(|factor| base.checked_mul(factor))
+    {
+        Some(d) if d <= max_delay => d,
+        _ => max_delay,
+    };
+
+    if jitter {
+        // full jitter: sample uniformly from [0, capped]
+        random_duration(capped)
+    } else {
+        capped
+    }
+}
+
+/// Compute the next decorrelated-jitter delay from the previous one:
+/// `min(cap, random_between(base, prev * 3))`.
+fn decorrelated_backoff(base: Duration, cap: Duration, prev: Duration) -> Duration {
+    let upper = prev.checked_mul(3).unwrap_or(Duration::MAX);
+    // `random_duration_between` yields `base` when `upper` falls below it.
+    random_duration_between(base, upper).min(cap)
+}
+
+/// Sample a [`Duration`] uniformly from `[0, max]`.
+fn random_duration(max: Duration) -> Duration {
+    random_duration_between(Duration::ZERO, max)
+}
+
+/// Sample a [`Duration`] uniformly from `[min, max]`.
+fn random_duration_between(min: Duration, max: Duration) -> Duration {
+    let lo = min.as_nanos();
+    let hi = max.as_nanos();
+    if hi <= lo {
+        return min;
     }
-    duration
+    let sampled = rand::thread_rng().gen_range(lo..=hi);
+    // `sampled <= max` so it fits back into the range a Duration can represent.
+    Duration::new((sampled / 1_000_000_000) as u64, (sampled % 1_000_000_000) as u32)
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -91,7 +414,7 @@ pub enum Outcome {
 
 #[cfg(test)]
 mod tests {
-    use super::{Outcome, RequestStrategy};
+    use super::{Outcome, RequestStrategy, RetryConfig};
     use std::time::Duration;
 
     #[test]
@@ -104,8 +427,8 @@ mod tests {
     fn test_once_strategy() {
         let strategy = RequestStrategy::Once;
         assert_eq!(strategy.get_key(), None);
-        assert_eq!(strategy.test(None, None, 0), Outcome::Continue(None));
-        assert_eq!(strategy.test(None, None, 1), Outcome::Stop);
+        assert_eq!(strategy.test(None, None, 0, None, None, None, Duration::ZERO), Outcome::Continue(None));
+        assert_eq!(strategy.test(None, None, 1, None, None, None, Duration::ZERO), Outcome::Stop);
     }
 
     #[test]
@@ -126,20 +449,230 @@ mod tests {
     #[test]
     fn test_retry_strategy() {
         let strategy = RequestStrategy::Retry(3);
-        assert_eq!(strategy.test(None, None, 0), Outcome::Continue(None));
-        assert_eq!(strategy.test(None, None, 1), Outcome::Continue(None));
-        assert_eq!(strategy.test(None, None, 2), Outcome::Continue(None));
-        assert_eq!(strategy.test(None, None, 3), Outcome::Stop);
-        assert_eq!(strategy.test(None, None, 4), Outcome::Stop);
+        assert_eq!(strategy.test(None, None, 0, None, None, None, Duration::ZERO), Outcome::Continue(None));
+        assert_eq!(strategy.test(None, None, 1, None, None, None, Duration::ZERO), Outcome::Continue(None));
+        assert_eq!(strategy.test(None, None, 2, None, None, None, Duration::ZERO), Outcome::Continue(None));
+        assert_eq!(strategy.test(None, None, 3, None, None, None, Duration::ZERO), Outcome::Stop);
+        assert_eq!(strategy.test(None, None, 4, None, None, None, Duration::ZERO), Outcome::Stop);
     }
 
     #[test]
     fn test_backoff_strategy() {
-        let strategy = RequestStrategy::ExponentialBackoff(3);
-        assert_eq!(strategy.test(None, None, 0), Outcome::Continue(Some(Duration::from_secs(1))));
-        assert_eq!(strategy.test(None, None, 1), Outcome::Continue(Some(Duration::from_secs(2))));
-        assert_eq!(strategy.test(None, None, 2), Outcome::Continue(Some(Duration::from_secs(4))));
-        assert_eq!(strategy.test(None, None, 3), Outcome::Stop);
-        assert_eq!(strategy.test(None, None, 4), Outcome::Stop);
+        // jitter disabled so the schedule is deterministic
+        let strategy = RequestStrategy::ExponentialBackoff {
+            max_retries: 3,
+            base: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: false,
+            total_budget: None,
+        };
+        assert_eq!(
+            strategy.test(None, None, 0, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(1)))
+        );
+        assert_eq!(
+            strategy.test(None, None, 1, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(2)))
+        );
+        assert_eq!(
+            strategy.test(None, None, 2, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(4)))
+        );
+        assert_eq!(strategy.test(None, None, 3, None, None, None, Duration::ZERO), Outcome::Stop);
+        assert_eq!(strategy.test(None, None, 4, None, None, None, Duration::ZERO), Outcome::Stop);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let strategy = RequestStrategy::ExponentialBackoff {
+            max_retries: 100,
+            base: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: false,
+            total_budget: None,
+        };
+        // 2^20s would be ~12 days; the cap keeps it at max_delay.
+        assert_eq!(
+            strategy.test(None, None, 20, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(30)))
+        );
+    }
+
+    #[test]
+    fn test_backoff_jitter_within_cap() {
+        let strategy = RequestStrategy::exponential_backoff(10);
+        for retry in 0..6 {
+            match strategy.test(None, None, retry, None, None, None, Duration::ZERO) {
+                Outcome::Continue(Some(delay)) => {
+                    assert!(delay <= Duration::from_secs(30));
+                }
+                other => panic!("expected a jittered delay, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decorrelated_jitter_within_bounds() {
+        let base = Duration::from_secs(1);
+        let cap = Duration::from_secs(30);
+        let strategy = RequestStrategy::DecorrelatedJitter {
+            max_retries: 10,
+            base,
+            cap,
+            total_budget: None,
+        };
+
+        // feed each delay back into the next attempt, mirroring the retry loop.
+        let mut prev = None;
+        for retry in 0..8 {
+            match strategy.test(None, None, retry, prev, None, None, Duration::ZERO) {
+                Outcome::Continue(Some(delay)) => {
+                    assert!(delay >= base.min(cap));
+                    assert!(delay <= cap);
+                    prev = Some(delay);
+                }
+                other => panic!("expected a decorrelated delay, got {other:?}"),
+            }
+        }
+
+        assert_eq!(strategy.test(None, None, 10, prev, None, None, Duration::ZERO), Outcome::Stop);
+    }
+
+    #[test]
+    fn test_retry_if_predicate() {
+        use http_types::StatusCode;
+
+        // retry on 429 even though it's a client error, stop on other 4xx.
+        let strategy = RequestStrategy::Retry(3)
+            .retry_if(|status, _| status == StatusCode::TooManyRequests);
+
+        assert_eq!(
+            strategy.test(Some(StatusCode::TooManyRequests), None, 0, None, None, None, Duration::ZERO),
+            Outcome::Continue(None)
+        );
+        assert_eq!(
+            strategy.test(Some(StatusCode::BadRequest), None, 0, None, None, None, Duration::ZERO),
+            Outcome::Stop
+        );
+        // the inner max-attempt limit still applies to retried failures.
+        assert_eq!(
+            strategy.test(Some(StatusCode::TooManyRequests), None, 3, None, None, None, Duration::ZERO),
+            Outcome::Stop
+        );
+        // stripe's explicit veto still short-circuits the predicate.
+        assert_eq!(
+            strategy.test(Some(StatusCode::TooManyRequests), Some(false), 0, None, None, None, Duration::ZERO),
+            Outcome::Stop
+        );
+    }
+
+    #[test]
+    fn test_too_many_requests_is_retried() {
+        use http_types::StatusCode;
+
+        let strategy = RequestStrategy::exponential_backoff(3);
+        // other client errors still stop immediately...
+        assert_eq!(
+            strategy.test(Some(StatusCode::BadRequest), None, 0, None, None, None, Duration::ZERO),
+            Outcome::Stop
+        );
+        // ...but 429 falls through to the retry schedule.
+        assert!(matches!(
+            strategy.test(Some(StatusCode::TooManyRequests), None, 0, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_overrides_backoff() {
+        use http_types::StatusCode;
+
+        let strategy = RequestStrategy::ExponentialBackoff {
+            max_retries: 3,
+            base: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: false,
+            total_budget: None,
+        };
+        let retry_after = Some(Duration::from_secs(7));
+        // the server-provided delay wins over the computed backoff (2s).
+        assert_eq!(
+            strategy.test(Some(StatusCode::TooManyRequests), None, 1, None, None, retry_after, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(7)))
+        );
+        // a Retry-After never turns a Stop into a retry.
+        assert_eq!(
+            strategy.test(None, None, 3, None, None, retry_after, Duration::ZERO),
+            Outcome::Stop
+        );
+    }
+
+    #[test]
+    fn test_total_budget_clamps_and_stops() {
+        let strategy = RequestStrategy::ExponentialBackoff {
+            max_retries: 10,
+            base: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: false,
+            total_budget: Some(Duration::from_secs(10)),
+        };
+
+        // well within budget: the computed backoff (4s) is returned as-is.
+        assert_eq!(
+            strategy.test(None, None, 2, None, None, None, Duration::from_secs(3)),
+            Outcome::Continue(Some(Duration::from_secs(4)))
+        );
+        // near the ceiling: the 8s backoff is clamped to the 2s that remain.
+        assert_eq!(
+            strategy.test(None, None, 3, None, None, None, Duration::from_secs(8)),
+            Outcome::Continue(Some(Duration::from_secs(2)))
+        );
+        // budget exhausted: stop regardless of remaining attempts.
+        assert_eq!(
+            strategy.test(None, None, 1, None, None, None, Duration::from_secs(10)),
+            Outcome::Stop
+        );
+    }
+
+    #[test]
+    fn test_configured_strategy() {
+        // randomization disabled so delay = delay_factor * 2^attempt, capped.
+        let strategy = RetryConfig::new(3)
+            .delay_factor(Duration::from_millis(500))
+            .max_delay(Duration::from_secs(30))
+            .randomization_factor(0.0)
+            .strategy();
+
+        assert_eq!(
+            strategy.test(None, None, 0, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_millis(500)))
+        );
+        assert_eq!(
+            strategy.test(None, None, 2, None, None, None, Duration::ZERO),
+            Outcome::Continue(Some(Duration::from_secs(2)))
+        );
+        assert_eq!(
+            strategy.test(None, None, 3, None, None, None, Duration::ZERO),
+            Outcome::Stop
+        );
+    }
+
+    #[test]
+    fn test_configured_randomization_within_bounds() {
+        let strategy = RetryConfig::new(5)
+            .delay_factor(Duration::from_secs(1))
+            .randomization_factor(0.5)
+            .strategy();
+
+        for attempt in 0..4 {
+            match strategy.test(None, None, attempt, None, None, None, Duration::ZERO) {
+                Outcome::Continue(Some(delay)) => {
+                    let base = Duration::from_secs(1 << attempt);
+                    assert!(delay >= base.mul_f64(0.5));
+                    assert!(delay <= base.mul_f64(1.5));
+                }
+                other => panic!("expected a randomized delay, got {other:?}"),
+            }
+        }
     }
 }